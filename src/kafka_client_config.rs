@@ -0,0 +1,50 @@
+use rdkafka::ClientConfig;
+
+/// Applies SASL/TLS security settings for the Kafka admin client from environment variables,
+/// defaulting to `PLAINTEXT` when unset so local/dev clusters keep working without extra setup.
+/// `bootstrap.servers` is intentionally left untouched here; it's set per-resource from
+/// `spec.bootstrap_server` instead.
+pub fn apply_security_config(mut config: ClientConfig) -> ClientConfig {
+    let protocol =
+        env_or_file("APP__KAFKA__SECURITY_PROTOCOL").unwrap_or_else(|| "PLAINTEXT".to_string());
+    config.set("security.protocol", &protocol);
+
+    if let Some(mechanism) = env_or_file("APP__KAFKA__SASL_MECHANISM") {
+        config.set("sasl.mechanism", &mechanism);
+    }
+    if let Some(username) = env_or_file("APP__KAFKA__SASL_USERNAME") {
+        config.set("sasl.username", &username);
+    }
+    if let Some(password) = env_or_file("APP__KAFKA__SASL_PASSWORD") {
+        config.set("sasl.password", &password);
+    }
+    if let Some(ca_location) = env_or_file("APP__KAFKA__SSL_CA_LOCATION") {
+        config.set("ssl.ca.location", &ca_location);
+    }
+    if let Some(cert_location) = env_or_file("APP__KAFKA__SSL_CERTIFICATE_LOCATION") {
+        config.set("ssl.certificate.location", &cert_location);
+    }
+    if let Some(key_location) = env_or_file("APP__KAFKA__SSL_KEY_LOCATION") {
+        config.set("ssl.key.location", &key_location);
+    }
+
+    config
+}
+
+/// Reads `name` directly, or, if `<name>_FILE` is set instead, reads the value from that path.
+/// The `_FILE` form lets credentials be sourced from a mounted Kubernetes Secret so they can be
+/// rotated without redeploying the operator.
+fn env_or_file(name: &str) -> Option<String> {
+    if let Ok(value) = std::env::var(name) {
+        return Some(value);
+    }
+
+    let path = std::env::var(format!("{name}_FILE")).ok()?;
+    match std::fs::read_to_string(&path) {
+        Ok(contents) => Some(contents.trim().to_string()),
+        Err(e) => {
+            tracing::error!("Failed to read {} from {}: {:?}", name, path, e);
+            None
+        }
+    }
+}
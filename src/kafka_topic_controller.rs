@@ -1,5 +1,6 @@
 
 use crate::kafka_topic_helper::{KafkaTopicOps};
+use crate::metrics;
 use kube::api::{Patch, PatchParams};
 use kube::runtime::controller::Action;
 use kube::{Api, CustomResource, Resource, ResourceExt};
@@ -7,6 +8,7 @@ use kube_client::Client;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
+use std::collections::BTreeMap;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -17,7 +19,8 @@ use std::time::Duration;
     kind = "KafkaTopic",
     plural = "kafkatopics",
     derive = "PartialEq",
-    namespaced
+    namespaced,
+    status = "KafkaTopicStatus"
 )]
 #[serde(rename_all = "camelCase")]
 pub struct KafkaTopicSpec {
@@ -25,6 +28,23 @@ pub struct KafkaTopicSpec {
     pub topic: String,
     pub partitions: i32,
     pub replication_factor: i32,
+    /// Per-topic broker configuration, e.g. `retention.ms`, `cleanup.policy`,
+    /// `min.insync.replicas`. Entries are reconciled via `AlterConfigs`; keys
+    /// left unset here are never touched, so the broker default still applies.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config: Option<BTreeMap<String, String>>,
+}
+
+/// Observed convergence state, patched at the end of each create, update and no-op reconcile
+/// pass so `kubectl get kafkatopics` shows whether provisioning actually succeeded. A pass whose
+/// finalizer or Kafka admin call fails, and a delete pass, leave the previous status unchanged.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Default, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct KafkaTopicStatus {
+    pub observed_partitions: i32,
+    pub ready: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
 }
 
 pub trait KubeClientCrdOps {
@@ -35,6 +55,15 @@ pub trait KubeClientCrdOps {
         name: &str,
         namespace: &str,
     ) -> Result<KafkaTopic, kube::Error>;
+
+    /// Patches the `/status` subresource so the observed state survives independently
+    /// of `spec` edits and shows up in `kubectl get kafkatopics`.
+    async fn patch_status(
+        &self,
+        name: &str,
+        namespace: &str,
+        status: KafkaTopicStatus,
+    ) -> Result<KafkaTopic, kube::Error>;
 }
 
 pub(crate) struct KubeClient {
@@ -69,6 +98,18 @@ impl KubeClientCrdOps for KubeClient {
         let patch: Patch<&Value> = Patch::Merge(&finalizer);
         api.patch(name, &PatchParams::default(), &patch).await
     }
+
+    async fn patch_status(
+        &self,
+        name: &str,
+        namespace: &str,
+        status: KafkaTopicStatus,
+    ) -> Result<KafkaTopic, kube::Error> {
+        let api: Api<KafkaTopic> = Api::namespaced(self.client.clone(), namespace);
+        let patch: Value = json!({ "status": status });
+        api.patch_status(name, &PatchParams::default(), &Patch::Merge(&patch))
+            .await
+    }
 }
 
 pub(crate) struct ContextData<T: KafkaTopicOps, E: KubeClientCrdOps> {
@@ -85,15 +126,37 @@ impl<T: KafkaTopicOps, E: KubeClientCrdOps> ContextData<T, E> {
     }
 }
 
+#[derive(Debug)]
 enum KafkaTopicAction {
     /// Create the subresources and Kafka topics
     Create,
     /// Delete all subresources created in the `Create` phase
     Delete,
+    /// The topic exists but its broker config has drifted from `spec.config`
+    UpdateConfig,
+    /// The topic exists but its partition count has drifted from `spec.partitions`
+    UpdatePartitions,
     /// This `KafkaTopic` resource is in desired state and requires no actions to be taken
     NoOp,
 }
 
+impl KafkaTopicAction {
+    /// Short, stable label used as the `action` dimension on `metrics::RECONCILIATIONS_TOTAL`.
+    fn label(&self) -> &'static str {
+        match self {
+            KafkaTopicAction::Create => "create",
+            KafkaTopicAction::Delete => "delete",
+            KafkaTopicAction::UpdateConfig => "update_config",
+            KafkaTopicAction::UpdatePartitions => "update_partitions",
+            KafkaTopicAction::NoOp => "noop",
+        }
+    }
+}
+
+#[tracing::instrument(
+    skip(kafka_topic, context),
+    fields(name = %kafka_topic.name_any(), namespace = tracing::field::Empty, action = tracing::field::Empty)
+)]
 pub async fn reconcile<T: KafkaTopicOps, E: KubeClientCrdOps>(
     kafka_topic: Arc<KafkaTopic>,
     context: Arc<ContextData<T, E>>,
@@ -114,57 +177,131 @@ pub async fn reconcile<T: KafkaTopicOps, E: KubeClientCrdOps>(
         Some(namespace) => namespace,
     };
     let name = kafka_topic.name_any(); // Name of the Echo resource is used to name the subresources as well.
+    tracing::Span::current().record("namespace", namespace.as_str());
 
-    match determine_action(&kafka_topic) {
-        KafkaTopicAction::Create => {
-            // Creates a new CR with a Kafka Topic, but applies a finalizer first.
-            // Finalizer is applied first, as the operator might be shut down and restarted
-            // at any time, leaving subresources in intermediate state. This prevents leaks on
-            // the `KafkaTopic` resource deletion.
-            context.kube_client.add_finalizer(&name, &namespace).await?;
+    let action = determine_action(&kafka_topic, &context.kafka_topic_client).await?;
+    tracing::Span::current().record("action", action.label());
+    let action_label = action.label();
 
-            context.kafka_topic_client.create_topic(kafka_topic).await?;
-            Ok(Action::requeue(Duration::from_secs(10)))
-        }
-        KafkaTopicAction::Delete => {
-            // Deletes any subresources related to this `KafkaTopic` resources. If and only if all subresources
-            // are deleted, the finalizer is removed and Kubernetes is free to remove the `KafkaTopic` resource.
-            context.kafka_topic_client.delete_topic(kafka_topic).await?;
+    // Run the chosen action in its own async block so a failure is captured here as a `Result`
+    // instead of bailing out of `reconcile` via `?` before the outcome can be recorded below.
+    let result: Result<Action, Error> = async move {
+        match action {
+            KafkaTopicAction::Create => {
+                // Creates a new CR with a Kafka Topic, but applies a finalizer first.
+                // Finalizer is applied first, as the operator might be shut down and restarted
+                // at any time, leaving subresources in intermediate state. This prevents leaks on
+                // the `KafkaTopic` resource deletion.
+                context.kube_client.add_finalizer(&name, &namespace).await?;
+
+                context
+                    .kafka_topic_client
+                    .create_topic(kafka_topic.clone())
+                    .await?;
+
+                // Metadata isn't guaranteed to be visible cluster-wide the instant create_topics
+                // returns, so poll with backoff before reporting the topic as ready.
+                let status = context
+                    .kafka_topic_client
+                    .wait_for_topic_ready(kafka_topic)
+                    .await;
+                context.kube_client.patch_status(&name, &namespace, status).await?;
+                Ok(Action::requeue(Duration::from_secs(10)))
+            }
+            KafkaTopicAction::Delete => {
+                // Deletes any subresources related to this `KafkaTopic` resources. If and only if all subresources
+                // are deleted, the finalizer is removed and Kubernetes is free to remove the `KafkaTopic` resource.
+                context.kafka_topic_client.delete_topic(kafka_topic).await?;
+
+                context.kube_client.delete_finalizer(&name, &namespace).await?;
+                Ok(Action::await_change())
+            }
+            KafkaTopicAction::UpdatePartitions => {
+                // The topic exists but `spec.partitions` no longer matches the broker. Growing is
+                // handled here; shrinking is rejected by `set_partitions` itself since Kafka can't
+                // remove partitions from an existing topic.
+                context
+                    .kafka_topic_client
+                    .set_partitions(kafka_topic.clone())
+                    .await?;
+
+                let status = context.kafka_topic_client.topic_status(kafka_topic).await;
+                context.kube_client.patch_status(&name, &namespace, status).await?;
+                Ok(Action::requeue(Duration::from_secs(10)))
+            }
+            KafkaTopicAction::UpdateConfig => {
+                // The topic already exists, but `spec.config` has drifted from what the broker
+                // reports. Push only the changed keys and re-check after 10 seconds, so a drift
+                // that is still there is picked up again on the next pass.
+                context
+                    .kafka_topic_client
+                    .update_config(kafka_topic.clone())
+                    .await?;
 
-            context.kube_client.delete_finalizer(&name, &namespace).await?;
-            Ok(Action::await_change())
+                let status = context.kafka_topic_client.topic_status(kafka_topic).await;
+                context.kube_client.patch_status(&name, &namespace, status).await?;
+                Ok(Action::requeue(Duration::from_secs(10)))
+            }
+            // The resource is already in desired state. Refresh the status so it keeps reflecting
+            // reality (e.g. after an operator restart) and re-check after 10 seconds.
+            KafkaTopicAction::NoOp => {
+                let status = context.kafka_topic_client.topic_status(kafka_topic).await;
+                context.kube_client.patch_status(&name, &namespace, status).await?;
+                Ok(Action::requeue(Duration::from_secs(10)))
+            }
         }
-        // The resource is already in desired state, do nothing and re-check after 10 seconds
-        KafkaTopicAction::NoOp => Ok(Action::requeue(Duration::from_secs(10))),
     }
+    .await;
+
+    metrics::RECONCILIATIONS_TOTAL
+        .with_label_values(&[action_label, if result.is_ok() { "success" } else { "error" }])
+        .inc();
+    result
 }
 
 /// Resources arrives into reconciliation queue in a certain state. This function looks at
 /// the state of given `KafkaTopic` resource and decides which actions needs to be performed.
 /// The finite set of possible actions is represented by the `KafkaTopicAction` enum.
-fn determine_action(kafka_topic: &KafkaTopic) -> KafkaTopicAction {
+async fn determine_action<T: KafkaTopicOps>(
+    kafka_topic: &KafkaTopic,
+    kafka_topic_client: &T,
+) -> Result<KafkaTopicAction, Error> {
     if kafka_topic.meta().deletion_timestamp.is_some() {
-        KafkaTopicAction::Delete
-    } else if kafka_topic
+        return Ok(KafkaTopicAction::Delete);
+    }
+    if kafka_topic
         .meta()
         .finalizers
         .as_ref()
         .map_or(true, |finalizers| finalizers.is_empty())
     {
-        KafkaTopicAction::Create
-    } else {
-        KafkaTopicAction::NoOp
+        return Ok(KafkaTopicAction::Create);
     }
+    if !kafka_topic_client
+        .partitions_match(Arc::new(kafka_topic.clone()))
+        .await?
+    {
+        return Ok(KafkaTopicAction::UpdatePartitions);
+    }
+    if kafka_topic.spec.config.is_some()
+        && kafka_topic_client
+            .config_drifted(Arc::new(kafka_topic.clone()))
+            .await?
+    {
+        return Ok(KafkaTopicAction::UpdateConfig);
+    }
+    Ok(KafkaTopicAction::NoOp)
 }
 
 /// Actions to be taken when a reconciliation fails - for whatever reason.
-/// Prints out the error to `stderr` and requeues the resource for another reconciliation.
+/// Logs the error as a structured event and requeues the resource for another reconciliation.
 pub(crate) fn on_error<T: KafkaTopicOps, E: KubeClientCrdOps>(
     echo: Arc<KafkaTopic>,
     error: &Error,
     _context: Arc<ContextData<T, E>>,
 ) -> Action {
-    eprintln!("Reconciliation error:\n{:?}.\n{:?}", error, echo);
+    tracing::error!(name = %echo.name_any(), error = ?error, "Reconciliation failed");
+    metrics::RECONCILE_ERRORS_TOTAL.inc();
     Action::requeue(Duration::from_secs(5))
 }
 
@@ -179,13 +316,47 @@ pub enum Error {
     /// Error in user input or Echo resource definition, typically missing fields.
     #[error("Invalid Echo CRD: {0}")]
     UserInputError(String),
+    /// A Kafka admin operation (create/delete/describe/alter/create-partitions) failed, or the
+    /// admin client for the resource's bootstrap server could not be created.
+    #[error("Kafka admin operation on topic '{topic}' failed: {reason}")]
+    KafkaAdminError { topic: String, reason: String },
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::sync::Arc;
-    use tokio::sync::Mutex;
+
+    struct KafkaTopicMock {
+        kafka_topic: KafkaTopic,
+    }
+
+    impl KubeClientCrdOps for KafkaTopicMock {
+        async fn add_finalizer(
+            &self,
+            _name: &str,
+            _namespace: &str,
+        ) -> Result<KafkaTopic, kube_client::Error> {
+            Ok(self.kafka_topic.clone())
+        }
+
+        async fn delete_finalizer(
+            &self,
+            _name: &str,
+            _namespace: &str,
+        ) -> Result<KafkaTopic, kube_client::Error> {
+            Ok(self.kafka_topic.clone())
+        }
+
+        async fn patch_status(
+            &self,
+            _name: &str,
+            _namespace: &str,
+            _status: KafkaTopicStatus,
+        ) -> Result<KafkaTopic, kube_client::Error> {
+            Ok(self.kafka_topic.clone())
+        }
+    }
 
     #[tokio::test]
     async fn test_reconcile_create_action() {
@@ -194,17 +365,58 @@ mod tests {
         impl KafkaTopicOps for KafkaTopicCLientMock {
             async fn create_topic(
                 &self,
-                kafka_topic: Arc<KafkaTopic>,
-            ) -> Result<(), kube_client::Error> {
+                _kafka_topic: Arc<KafkaTopic>,
+            ) -> Result<(), Error> {
                 Ok(())
             }
 
             async fn delete_topic(
                 &self,
-                kafka_topic: Arc<KafkaTopic>,
-            ) -> Result<(), kube_client::Error> {
+                _kafka_topic: Arc<KafkaTopic>,
+            ) -> Result<(), Error> {
+                Ok(())
+            }
+
+            async fn config_drifted(
+                &self,
+                _kafka_topic: Arc<KafkaTopic>,
+            ) -> Result<bool, Error> {
+                Ok(false)
+            }
+
+            async fn update_config(
+                &self,
+                _kafka_topic: Arc<KafkaTopic>,
+            ) -> Result<(), Error> {
+                Ok(())
+            }
+
+            async fn partitions_match(
+                &self,
+                _kafka_topic: Arc<KafkaTopic>,
+            ) -> Result<bool, Error> {
+                Ok(true)
+            }
+
+            async fn set_partitions(&self, _kafka_topic: Arc<KafkaTopic>) -> Result<(), Error> {
                 Ok(())
             }
+
+            async fn wait_for_topic_ready(&self, kafka_topic: Arc<KafkaTopic>) -> KafkaTopicStatus {
+                KafkaTopicStatus {
+                    observed_partitions: kafka_topic.spec.partitions,
+                    ready: true,
+                    last_error: None,
+                }
+            }
+
+            async fn topic_status(&self, kafka_topic: Arc<KafkaTopic>) -> KafkaTopicStatus {
+                KafkaTopicStatus {
+                    observed_partitions: kafka_topic.spec.partitions,
+                    ready: true,
+                    last_error: None,
+                }
+            }
         }
         let kafka_topic_spec = KafkaTopic {
             metadata: kube::core::ObjectMeta {
@@ -218,45 +430,152 @@ mod tests {
                 topic: "test-topic".to_string(),
                 partitions: 3,
                 replication_factor: 1,
+                config: None,
             },
+            status: None,
         };
 
-        struct KafkaTopicMock {
-            kafka_topic: KafkaTopic,
-        }
         let kafka_topic_mock = KafkaTopicMock {
             kafka_topic: kafka_topic_spec.clone(),
         };
 
-        impl KubeClientCrdOps for KafkaTopicMock {
-            async fn add_finalizer(
-                &self,
-                name: &str,
-                namespace: &str,
-            ) -> Result<KafkaTopic, kube_client::Error> {
-                Ok(self.kafka_topic.clone())
+        let data = ContextData::new(KafkaTopicCLientMock {}, kafka_topic_mock);
+        let context = Arc::new(data);
+
+        // Call reconcile
+        let result = reconcile(Arc::new(kafka_topic_spec.clone()), context.clone()).await;
+
+        // Assert
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), Action::requeue(Duration::from_secs(10)));
+    }
+
+    struct KafkaTopicPartitionMock {
+        current_partitions: i32,
+    }
+
+    impl KafkaTopicOps for KafkaTopicPartitionMock {
+        async fn create_topic(&self, _kafka_topic: Arc<KafkaTopic>) -> Result<(), Error> {
+            Ok(())
+        }
+
+        async fn delete_topic(&self, _kafka_topic: Arc<KafkaTopic>) -> Result<(), Error> {
+            Ok(())
+        }
+
+        async fn config_drifted(&self, _kafka_topic: Arc<KafkaTopic>) -> Result<bool, Error> {
+            Ok(false)
+        }
+
+        async fn update_config(&self, _kafka_topic: Arc<KafkaTopic>) -> Result<(), Error> {
+            Ok(())
+        }
+
+        async fn partitions_match(
+            &self,
+            kafka_topic: Arc<KafkaTopic>,
+        ) -> Result<bool, Error> {
+            Ok(self.current_partitions == kafka_topic.spec.partitions)
+        }
+
+        async fn set_partitions(&self, kafka_topic: Arc<KafkaTopic>) -> Result<(), Error> {
+            if kafka_topic.spec.partitions < self.current_partitions {
+                return Err(Error::UserInputError(format!(
+                    "Cannot shrink topic '{}' from {} to {} partitions",
+                    kafka_topic.spec.topic, self.current_partitions, kafka_topic.spec.partitions
+                )));
             }
+            Ok(())
+        }
 
-            async fn delete_finalizer(
-                &self,
-                name: &str,
-                namespace: &str,
-            ) -> Result<KafkaTopic, kube_client::Error> {
-                Ok(self.kafka_topic.clone())
+        async fn wait_for_topic_ready(&self, kafka_topic: Arc<KafkaTopic>) -> KafkaTopicStatus {
+            KafkaTopicStatus {
+                observed_partitions: kafka_topic.spec.partitions,
+                ready: true,
+                last_error: None,
             }
         }
 
-        let data = ContextData::new(KafkaTopicCLientMock {}, kafka_topic_mock);
-        let context = Arc::new(data);
+        async fn topic_status(&self, kafka_topic: Arc<KafkaTopic>) -> KafkaTopicStatus {
+            KafkaTopicStatus {
+                observed_partitions: self.current_partitions,
+                ready: self.current_partitions == kafka_topic.spec.partitions,
+                last_error: None,
+            }
+        }
+    }
+
+    fn partition_test_topic(desired_partitions: i32) -> KafkaTopic {
+        KafkaTopic {
+            metadata: kube::core::ObjectMeta {
+                name: Some("test-topic".to_string()),
+                namespace: Some("default".to_string()),
+                finalizers: Some(vec!["arnecdn.github.com/finalizer".to_string()]),
+                ..Default::default()
+            },
+            spec: KafkaTopicSpec {
+                bootstrap_server: "offline:9092".to_string(),
+                topic: "test-topic".to_string(),
+                partitions: desired_partitions,
+                replication_factor: 1,
+                config: None,
+            },
+            status: None,
+        }
+    }
 
-        // Mock helper behavior
-        let create_called = Arc::new(Mutex::new(false));
-        let create_called_clone = create_called.clone();
+    #[tokio::test]
+    async fn test_reconcile_grows_partitions() {
+        let kafka_topic = partition_test_topic(6);
+        let kube_client_mock = KafkaTopicMock {
+            kafka_topic: kafka_topic.clone(),
+        };
+        let data = ContextData::new(
+            KafkaTopicPartitionMock {
+                current_partitions: 3,
+            },
+            kube_client_mock,
+        );
 
-        // Call reconcile
-        let result = reconcile(Arc::new(kafka_topic_spec.clone()), context.clone()).await;
+        let result = reconcile(Arc::new(kafka_topic), Arc::new(data)).await;
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), Action::requeue(Duration::from_secs(10)));
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_rejects_partition_shrink() {
+        let kafka_topic = partition_test_topic(1);
+        let kube_client_mock = KafkaTopicMock {
+            kafka_topic: kafka_topic.clone(),
+        };
+        let data = ContextData::new(
+            KafkaTopicPartitionMock {
+                current_partitions: 3,
+            },
+            kube_client_mock,
+        );
+
+        let result = reconcile(Arc::new(kafka_topic), Arc::new(data)).await;
+
+        assert!(matches!(result, Err(Error::UserInputError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_noop_when_partitions_equal() {
+        let kafka_topic = partition_test_topic(3);
+        let kube_client_mock = KafkaTopicMock {
+            kafka_topic: kafka_topic.clone(),
+        };
+        let data = ContextData::new(
+            KafkaTopicPartitionMock {
+                current_partitions: 3,
+            },
+            kube_client_mock,
+        );
+
+        let result = reconcile(Arc::new(kafka_topic), Arc::new(data)).await;
 
-        // Assert
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), Action::requeue(Duration::from_secs(10)));
     }
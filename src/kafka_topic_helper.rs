@@ -1,90 +1,437 @@
-use crate::kafka_topic_controller::KafkaTopic;
-use kube::Error;
-use rdkafka::admin::{AdminClient, AdminOptions, NewTopic, TopicReplication};
+use crate::kafka_topic_controller::Error as ControllerError;
+use crate::kafka_topic_controller::{KafkaTopic, KafkaTopicStatus};
+use crate::metrics;
+use rdkafka::admin::{
+    AdminClient, AdminOptions, AlterConfig, ConfigSource, NewPartitions, NewTopic,
+    ResourceSpecifier, TopicReplication,
+};
 use rdkafka::client::DefaultClientContext;
+use rdkafka::types::RDKafkaErrorCode;
+use rdkafka::ClientConfig;
+use std::collections::BTreeMap;
 use std::sync::Arc;
-use log::{error, info};
+use tracing::{error, info};
 
 
 //
 pub trait KafkaTopicOps {
-    async fn create_topic(&self, kafka_topic: Arc<KafkaTopic>) -> Result<(), Error>;
-    async fn delete_topic(&self, kafka_topic: Arc<KafkaTopic>) -> Result<(), Error>;
-    async fn topic_exists(&self, kafka_topic: Arc<KafkaTopic>) -> Result<bool, Error>;
+    async fn create_topic(&self, kafka_topic: Arc<KafkaTopic>) -> Result<(), ControllerError>;
+    async fn delete_topic(&self, kafka_topic: Arc<KafkaTopic>) -> Result<(), ControllerError>;
+    /// Compares `spec.config` against the broker's current dynamic config for the topic,
+    /// ignoring entries that are still at their default (i.e. never set by us).
+    async fn config_drifted(&self, kafka_topic: Arc<KafkaTopic>) -> Result<bool, ControllerError>;
+    /// Pushes the subset of `spec.config` that differs from the broker's current config.
+    async fn update_config(&self, kafka_topic: Arc<KafkaTopic>) -> Result<(), ControllerError>;
+    /// Compares the topic's actual partition count against `spec.partitions`.
+    async fn partitions_match(&self, kafka_topic: Arc<KafkaTopic>) -> Result<bool, ControllerError>;
+    /// Grows the topic to `spec.partitions` via `create_partitions`. `spec.partitions` is the
+    /// new *total*, not a delta. Kafka can't shrink partition counts, so a lower desired count
+    /// is rejected with `Error::UserInputError` instead of being attempted against the broker.
+    async fn set_partitions(
+        &self,
+        kafka_topic: Arc<KafkaTopic>,
+    ) -> Result<(), ControllerError>;
+    /// Polls the broker with bounded exponential backoff until the topic and its expected
+    /// partition count are visible, or gives up and reports the timeout in `last_error`.
+    async fn wait_for_topic_ready(&self, kafka_topic: Arc<KafkaTopic>) -> KafkaTopicStatus;
+    /// Single-shot read of the topic's current convergence state, used to refresh status
+    /// outside of the initial creation wait.
+    async fn topic_status(&self, kafka_topic: Arc<KafkaTopic>) -> KafkaTopicStatus;
 }
 
 pub struct KafkaAdminClient {
-    pub(crate) inner_kafka_client: AdminClient<DefaultClientContext>,
+    /// Security settings (SASL/TLS) only; `bootstrap.servers` is deliberately left unset here
+    /// and applied per-resource from `spec.bootstrap_server`, so a single operator instance can
+    /// manage topics across multiple Kafka clusters.
+    pub(crate) client_config: ClientConfig,
 }
 
-impl KafkaTopicOps for KafkaAdminClient {
+impl KafkaAdminClient {
+    fn admin_client_for(
+        &self,
+        kafka_topic: &KafkaTopic,
+    ) -> Result<AdminClient<DefaultClientContext>, ControllerError> {
+        self.client_config
+            .clone()
+            .set("bootstrap.servers", &kafka_topic.spec.bootstrap_server)
+            .create()
+            .map_err(|e| ControllerError::KafkaAdminError {
+                topic: kafka_topic.spec.topic.clone(),
+                reason: format!(
+                    "failed to create admin client for bootstrap server '{}': {}",
+                    kafka_topic.spec.bootstrap_server, e
+                ),
+            })
+    }
+
+    /// Reads the topic's current partition count from cluster metadata. `fetch_metadata` is a
+    /// synchronous, blocking rdkafka call, so it's run via `spawn_blocking` to avoid parking a
+    /// Tokio worker thread. Returns `None` if the topic isn't visible yet, the broker can't be
+    /// reached, or the admin client itself couldn't be created.
+    async fn observed_partitions(&self, kafka_topic: &KafkaTopic) -> Option<i32> {
+        let admin = self.admin_client_for(kafka_topic).ok()?;
+        let topic = kafka_topic.spec.topic.clone();
+        tokio::task::spawn_blocking(move || {
+            match admin
+                .inner()
+                .fetch_metadata(Some(&topic), std::time::Duration::from_secs(5))
+            {
+                Ok(metadata) => metadata
+                    .topics()
+                    .iter()
+                    .find(|t| t.name() == topic)
+                    // A topic the broker doesn't know yet comes back as an entry carrying an error.
+                    .filter(|t| t.error().is_none())
+                    .map(|t| t.partitions().len() as i32),
+                Err(e) => {
+                    error!("Failed to fetch metadata for topic {}: {:?}", topic, e);
+                    None
+                }
+            }
+        })
+        .await
+        .unwrap_or_else(|e| {
+            error!("observed_partitions task panicked: {:?}", e);
+            None
+        })
+    }
+}
 
+impl KafkaTopicOps for KafkaAdminClient {
 
+    #[tracing::instrument(skip(self, kafka_topic), fields(broker = %kafka_topic.spec.bootstrap_server, topic = %kafka_topic.spec.topic))]
     async fn create_topic(
         &self,
         kafka_topic: Arc<KafkaTopic>,
-    ) -> Result<(), Error> {
-        let new_topics = vec![NewTopic::new(
+    ) -> Result<(), ControllerError> {
+        let admin = self.admin_client_for(&kafka_topic)?;
+        let _timer = metrics::ADMIN_OPERATION_DURATION_SECONDS
+            .with_label_values(&["create_topic"])
+            .start_timer();
+
+        let mut new_topic = NewTopic::new(
             &*kafka_topic.spec.topic,
             kafka_topic.spec.partitions,
             TopicReplication::Fixed(kafka_topic.spec.replication_factor),
-        )];
-        let res = self.inner_kafka_client.create_topics(&new_topics, &AdminOptions::new());
-
-        match futures::executor::block_on(res) {
-            Ok(results) => {
-                for r in results {
-                    match r {
-                        Ok(topic) => info!("Created topic: {}", topic),
-                        Err((topic, err)) => println!("Failed to create topic {}: {:?}", topic, err),
-                    }
+        );
+        if let Some(config) = &kafka_topic.spec.config {
+            for (key, value) in config {
+                new_topic = new_topic.set(key, value);
+            }
+        }
+        let new_topics = vec![new_topic];
+        let results = admin
+            .create_topics(&new_topics, &AdminOptions::new())
+            .await
+            .map_err(|e| {
+                metrics::ADMIN_OPERATION_ERRORS_TOTAL
+                    .with_label_values(&["create_topic"])
+                    .inc();
+                ControllerError::KafkaAdminError {
+                    topic: kafka_topic.spec.topic.clone(),
+                    reason: e.to_string(),
+                }
+            })?;
+
+        for r in results {
+            match r {
+                Ok(topic) => info!("Created topic: {}", topic),
+                // The topic is already there, which is exactly what we wanted.
+                Err((topic, RDKafkaErrorCode::TopicAlreadyExists)) => {
+                    info!("Topic {} already exists, treating create as converged", topic)
+                }
+                Err((topic, err)) => {
+                    metrics::ADMIN_OPERATION_ERRORS_TOTAL
+                        .with_label_values(&["create_topic"])
+                        .inc();
+                    return Err(ControllerError::KafkaAdminError {
+                        topic,
+                        reason: err.to_string(),
+                    })
                 }
             }
-            Err(e) => println!("Admin operation failed: {:?}", e),
         }
         Ok(())
     }
 
+    #[tracing::instrument(skip(self, kafka_topic), fields(broker = %kafka_topic.spec.bootstrap_server, topic = %kafka_topic.spec.topic))]
     async fn delete_topic(
         &self,
         kafka_topic: Arc<KafkaTopic>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), ControllerError> {
+        let admin = self.admin_client_for(&kafka_topic)?;
         let delete_admin =
             &AdminOptions::new().operation_timeout(Some(std::time::Duration::from_secs(30)));
+        let _timer = metrics::ADMIN_OPERATION_DURATION_SECONDS
+            .with_label_values(&["delete_topic"])
+            .start_timer();
 
-        let res = self.inner_kafka_client.delete_topics(&[&*kafka_topic.spec.topic], delete_admin);
+        let results = admin
+            .delete_topics(&[&*kafka_topic.spec.topic], delete_admin)
+            .await
+            .map_err(|e| {
+                metrics::ADMIN_OPERATION_ERRORS_TOTAL
+                    .with_label_values(&["delete_topic"])
+                    .inc();
+                ControllerError::KafkaAdminError {
+                    topic: kafka_topic.spec.topic.clone(),
+                    reason: e.to_string(),
+                }
+            })?;
 
-        match futures::executor::block_on(res) {
-            Ok(results) => {
-                for r in results {
-                    match r {
-                        Ok(topic) => println!("Deleted topic: {}", topic),
-                        Err((topic, err)) => println!("Failed to create topic {}: {:?}", topic, err),
-                    }
+        for r in results {
+            match r {
+                Ok(topic) => info!("Deleted topic: {}", topic),
+                // The topic is already gone, which is exactly what we wanted.
+                Err((topic, RDKafkaErrorCode::UnknownTopicOrPartition)) => {
+                    info!("Topic {} already gone, treating delete as converged", topic)
+                }
+                Err((topic, err)) => {
+                    metrics::ADMIN_OPERATION_ERRORS_TOTAL
+                        .with_label_values(&["delete_topic"])
+                        .inc();
+                    return Err(ControllerError::KafkaAdminError {
+                        topic,
+                        reason: err.to_string(),
+                    })
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn config_drifted(&self, kafka_topic: Arc<KafkaTopic>) -> Result<bool, ControllerError> {
+        let Some(desired) = &kafka_topic.spec.config else {
+            return Ok(false);
+        };
+        let admin = self.admin_client_for(&kafka_topic)?;
+        let current = describe_topic_config(&admin, &kafka_topic.spec.topic).await?;
+        Ok(desired
+            .iter()
+            .any(|(key, value)| current.get(key) != Some(value)))
+    }
+
+    #[tracing::instrument(skip(self, kafka_topic), fields(broker = %kafka_topic.spec.bootstrap_server, topic = %kafka_topic.spec.topic))]
+    async fn update_config(&self, kafka_topic: Arc<KafkaTopic>) -> Result<(), ControllerError> {
+        let Some(desired) = &kafka_topic.spec.config else {
+            return Ok(());
+        };
+        let admin = self.admin_client_for(&kafka_topic)?;
+        let current = describe_topic_config(&admin, &kafka_topic.spec.topic).await?;
+        let mut alter_config = AlterConfig::new(ResourceSpecifier::Topic(&kafka_topic.spec.topic));
+        for (key, value) in desired {
+            if current.get(key) != Some(value) {
+                alter_config = alter_config.set(key, value);
+            }
+        }
+
+        let _timer = metrics::ADMIN_OPERATION_DURATION_SECONDS
+            .with_label_values(&["update_config"])
+            .start_timer();
+        let results = admin
+            .alter_configs(&[alter_config], &AdminOptions::new())
+            .await
+            .map_err(|e| {
+                metrics::ADMIN_OPERATION_ERRORS_TOTAL
+                    .with_label_values(&["update_config"])
+                    .inc();
+                ControllerError::KafkaAdminError {
+                    topic: kafka_topic.spec.topic.clone(),
+                    reason: e.to_string(),
+                }
+            })?;
+
+        for r in results {
+            match r {
+                Ok(resource) => info!("Updated config for {:?}", resource),
+                Err((resource, err)) => {
+                    metrics::ADMIN_OPERATION_ERRORS_TOTAL
+                        .with_label_values(&["update_config"])
+                        .inc();
+                    return Err(ControllerError::KafkaAdminError {
+                        topic: kafka_topic.spec.topic.clone(),
+                        reason: format!("{:?}: {:?}", resource, err),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn partitions_match(&self, kafka_topic: Arc<KafkaTopic>) -> Result<bool, ControllerError> {
+        // Unable to tell, so don't churn on every reconcile until the cluster is reachable.
+        match self.observed_partitions(&kafka_topic).await {
+            Some(current) => Ok(current == kafka_topic.spec.partitions),
+            None => Ok(true),
+        }
+    }
+
+    #[tracing::instrument(skip(self, kafka_topic), fields(broker = %kafka_topic.spec.bootstrap_server, topic = %kafka_topic.spec.topic))]
+    async fn set_partitions(
+        &self,
+        kafka_topic: Arc<KafkaTopic>,
+    ) -> Result<(), ControllerError> {
+        let admin = self.admin_client_for(&kafka_topic)?;
+        let topic = &kafka_topic.spec.topic;
+        let desired = kafka_topic.spec.partitions;
+
+        let Some(current) = self.observed_partitions(&kafka_topic).await else {
+            return Ok(());
+        };
+
+        if desired < current {
+            return Err(ControllerError::UserInputError(format!(
+                "Cannot shrink topic '{}' from {} to {} partitions: Kafka does not support removing partitions",
+                topic, current, desired
+            )));
+        }
+        if desired == current {
+            return Ok(());
+        }
+
+        let _timer = metrics::ADMIN_OPERATION_DURATION_SECONDS
+            .with_label_values(&["set_partitions"])
+            .start_timer();
+        let new_partitions = [NewPartitions::new(topic, desired as usize)];
+        let results = admin
+            .create_partitions(&new_partitions, &AdminOptions::new())
+            .await
+            .map_err(|e| {
+                metrics::ADMIN_OPERATION_ERRORS_TOTAL
+                    .with_label_values(&["set_partitions"])
+                    .inc();
+                ControllerError::KafkaAdminError {
+                    topic: topic.clone(),
+                    reason: e.to_string(),
+                }
+            })?;
+
+        for r in results {
+            match r {
+                Ok(topic) => info!("Scaled topic {} to {} partitions", topic, desired),
+                Err((topic, err)) => {
+                    metrics::ADMIN_OPERATION_ERRORS_TOTAL
+                        .with_label_values(&["set_partitions"])
+                        .inc();
+                    return Err(ControllerError::KafkaAdminError {
+                        topic,
+                        reason: err.to_string(),
+                    })
                 }
             }
-            Err(e) => println!("Admin operation failed: {:?}", e),
         }
         Ok(())
     }
 
-    async fn topic_exists(&self, kafka_topic: Arc<KafkaTopic>) -> Result<bool, Error> {
+    async fn wait_for_topic_ready(&self, kafka_topic: Arc<KafkaTopic>) -> KafkaTopicStatus {
+        let topic = &kafka_topic.spec.topic;
+        let desired_partitions = kafka_topic.spec.partitions;
+
+        // Fail fast if the admin client can't even be created, rather than looping until the
+        // deadline. An unreachable broker is not detected here; it shows up as a timeout below.
+        if let Err(e) = self.admin_client_for(&kafka_topic) {
+            return KafkaTopicStatus {
+                observed_partitions: 0,
+                ready: false,
+                last_error: Some(e.to_string()),
+            };
+        }
+
+        let deadline = std::time::Duration::from_secs(30);
+        let backoff_cap = std::time::Duration::from_secs(5);
+        let mut backoff = std::time::Duration::from_millis(100);
+        let mut waited = std::time::Duration::ZERO;
+
+        loop {
+            if let Some(observed) = self.observed_partitions(&kafka_topic).await {
+                if observed >= desired_partitions {
+                    return KafkaTopicStatus {
+                        observed_partitions: observed,
+                        ready: true,
+                        last_error: None,
+                    };
+                }
+            }
+
+            if waited >= deadline {
+                return KafkaTopicStatus {
+                    observed_partitions: 0,
+                    ready: false,
+                    last_error: Some(format!(
+                        "Timed out after {:?} waiting for topic '{}' to converge",
+                        deadline, topic
+                    )),
+                };
+            }
+
+            tokio::time::sleep(backoff).await;
+            waited += backoff;
+            backoff = std::cmp::min(backoff * 2, backoff_cap);
+        }
+    }
+
+    async fn topic_status(&self, kafka_topic: Arc<KafkaTopic>) -> KafkaTopicStatus {
+        let topic = &kafka_topic.spec.topic;
+        if let Err(e) = self.admin_client_for(&kafka_topic) {
+            return KafkaTopicStatus {
+                observed_partitions: 0,
+                ready: false,
+                last_error: Some(e.to_string()),
+            };
+        }
 
-        let res = self.inner_kafka_client.inner()
-            .fetch_metadata(None, std::time::Duration::from_secs(5));
+        match self.observed_partitions(&kafka_topic).await {
+            Some(observed) => KafkaTopicStatus {
+                observed_partitions: observed,
+                ready: observed == kafka_topic.spec.partitions,
+                last_error: None,
+            },
+            None => KafkaTopicStatus {
+                observed_partitions: 0,
+                ready: false,
+                last_error: Some(format!("Topic '{}' not found on broker", topic)),
+            },
+        }
+    }
+}
 
-        match res {
-            Ok(metadata) => {
-                if metadata.topics().iter().any(|t| t.name() == kafka_topic.spec.topic) {
-                    Ok(true)
-                } else {
-                    info!("Topic {} not found", kafka_topic.spec.topic);
-                    Ok(false)
+/// Reads the topic's current dynamic config from the broker via `describe_configs`, skipping
+/// entries that are still at their `ConfigSource::DefaultConfig` value since those were never
+/// set by us and shouldn't be fought over. A failed describe is returned as an error rather than
+/// an empty map, so callers don't mistake "broker unreachable" for "no config set".
+async fn describe_topic_config(
+    admin: &AdminClient<DefaultClientContext>,
+    topic: &str,
+) -> Result<BTreeMap<String, String>, ControllerError> {
+    let resources = [ResourceSpecifier::Topic(topic)];
+    let results = admin
+        .describe_configs(&resources, &AdminOptions::new())
+        .await
+        .map_err(|e| ControllerError::KafkaAdminError {
+            topic: topic.to_string(),
+            reason: format!("failed to describe config: {}", e),
+        })?;
+
+    let mut current = BTreeMap::new();
+    for result in results {
+        match result {
+            Ok(resource) => {
+                for entry in resource.entries {
+                    if entry.source == ConfigSource::DefaultConfig {
+                        continue;
+                    }
+                    if let Some(value) = entry.value {
+                        current.insert(entry.name, value);
+                    }
                 }
             }
-            Err(e) => {
-                error!("Topic {} not found. Error: {}", kafka_topic.spec.topic, e);
-                Ok(false) },
+            Err((resource, err)) => {
+                return Err(ControllerError::KafkaAdminError {
+                    topic: topic.to_string(),
+                    reason: format!("failed to describe config for {:?}: {:?}", resource, err),
+                });
+            }
         }
     }
+    Ok(current)
 }
@@ -8,13 +8,19 @@ use kube::{Api, Client};
 use rdkafka::ClientConfig;
 use std::sync::Arc;
 
+mod kafka_client_config;
 mod kafka_topic_controller;
 mod kafka_topic_helper;
+mod metrics;
+mod metrics_server;
 
 #[tokio::main]
 async fn main() {
     dotenv().ok();
-    env_logger::init();
+    // Structured, OpenTelemetry-compatible spans/events instead of `env_logger`. Plugging in an
+    // OTel layer here (`tracing-opentelemetry`) would let this operator's traces join the same
+    // collector as the Kafka clients it provisions for.
+    tracing_subscriber::fmt::init();
     // First, a Kubernetes client must be obtained using the `kube` crate
     // The client will later be moved to the custom controller
     let kubernetes_client: Client = Client::try_default()
@@ -24,28 +30,34 @@ async fn main() {
     // Preparation of resources used by the `kube_runtime::Controller`
     let crd_api: Api<KafkaTopic> = Api::all(kubernetes_client.clone());
 
+    // `bootstrap.servers` is deliberately not set here: each `KafkaTopic` carries its own
+    // `spec.bootstrap_server`, so a single operator instance can manage topics across multiple
+    // clusters. Only the security settings (SASL/TLS) are shared across all of them.
     let topic_client = KafkaAdminClient {
-        admin: ClientConfig::new()
-            .set(
-                "bootstrap.servers",
-                std::env::var("APP__KAFKA__BROKER")
-                    .expect("Missing environmentvariable for Kafkas bootstrap.server"),
-            )
-            .create()
-            .expect("Admin client creation failed"),
+        client_config: kafka_client_config::apply_security_config(ClientConfig::new()),
     };
     let kube_client = kafka_topic_controller::KubeClient {
-        client: Client::try_default().await.unwrap(),
+        client: kubernetes_client,
     };
 
     let data = ContextData::new(topic_client, kube_client);
     let context = Arc::new(data);
-    
+
+    let metrics_addr: std::net::SocketAddr = std::env::var("APP__METRICS__ADDR")
+        .unwrap_or_else(|_| "0.0.0.0:8080".to_string())
+        .parse()
+        .expect("APP__METRICS__ADDR must be a valid socket address");
+    tokio::spawn(async move {
+        if let Err(e) = metrics_server::serve(metrics_addr).await {
+            tracing::error!(error = ?e, "Metrics server stopped");
+        }
+    });
+
     Controller::new(crd_api.clone(), Config::default())
         .run(reconcile, on_error, context)
         .for_each(|reconciliation_result| async move {
             if let Err(reconciliation_err) = reconciliation_result {
-                eprintln!("Reconciliation error: {:?}", reconciliation_err)
+                tracing::error!(error = ?reconciliation_err, "Reconciliation error");
             }
         })
         .await;
@@ -0,0 +1,70 @@
+use once_cell::sync::Lazy;
+use prometheus::{Encoder, HistogramVec, IntCounter, IntCounterVec, Registry, TextEncoder};
+
+static REGISTRY: Lazy<Registry> = Lazy::new(Registry::new);
+
+/// Reconcile passes, labeled by the `KafkaTopicAction` taken and whether it succeeded.
+pub static RECONCILIATIONS_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_counter_vec(
+        "kafka_topic_operator_reconciliations_total",
+        "Total number of reconcile passes, by action and outcome",
+        &["action", "outcome"],
+    )
+});
+
+/// Reconciles that returned an `Err` and were handed to `on_error`.
+pub static RECONCILE_ERRORS_TOTAL: Lazy<IntCounter> = Lazy::new(|| {
+    let counter = IntCounter::new(
+        "kafka_topic_operator_reconcile_errors_total",
+        "Total number of reconcile passes that failed",
+    )
+    .expect("metric can be created");
+    REGISTRY
+        .register(Box::new(counter.clone()))
+        .expect("metric can be registered");
+    counter
+});
+
+/// Latency of individual Kafka admin calls (create/delete/alter/create-partitions), by operation.
+pub static ADMIN_OPERATION_DURATION_SECONDS: Lazy<HistogramVec> = Lazy::new(|| {
+    let histogram = HistogramVec::new(
+        prometheus::HistogramOpts::new(
+            "kafka_topic_operator_admin_operation_duration_seconds",
+            "Latency of Kafka admin operations, by operation",
+        ),
+        &["operation"],
+    )
+    .expect("metric can be created");
+    REGISTRY
+        .register(Box::new(histogram.clone()))
+        .expect("metric can be registered");
+    histogram
+});
+
+/// Kafka admin calls that returned an error, by operation.
+pub static ADMIN_OPERATION_ERRORS_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_counter_vec(
+        "kafka_topic_operator_admin_operation_errors_total",
+        "Total number of failed Kafka admin operations, by operation",
+        &["operation"],
+    )
+});
+
+fn register_counter_vec(name: &str, help: &str, labels: &[&str]) -> IntCounterVec {
+    let counter = IntCounterVec::new(prometheus::Opts::new(name, help), labels)
+        .expect("metric can be created");
+    REGISTRY
+        .register(Box::new(counter.clone()))
+        .expect("metric can be registered");
+    counter
+}
+
+/// Renders all registered metrics in the Prometheus text exposition format for the `/metrics`
+/// route served alongside `Controller::run`.
+pub fn render() -> String {
+    let mut buffer = Vec::new();
+    TextEncoder::new()
+        .encode(&REGISTRY.gather(), &mut buffer)
+        .expect("metrics can be encoded");
+    String::from_utf8(buffer).expect("metrics are valid utf8")
+}
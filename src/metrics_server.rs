@@ -0,0 +1,38 @@
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+/// Serves `GET /metrics` in the Prometheus text format on `addr`, alongside `Controller::run`.
+/// Deliberately hand-rolled rather than pulling in a web framework for a single read-only route.
+pub async fn serve(addr: std::net::SocketAddr) -> std::io::Result<()> {
+    let listener = TcpListener::bind(addr).await?;
+    tracing::info!(%addr, "Serving /metrics");
+
+    loop {
+        let (mut socket, _) = listener.accept().await?;
+        tokio::spawn(async move {
+            let mut buf = [0u8; 1024];
+            let request_line = match socket.read(&mut buf).await {
+                Ok(n) => String::from_utf8_lossy(&buf[..n]).into_owned(),
+                Err(e) => {
+                    tracing::error!(error = ?e, "Failed to read /metrics request");
+                    return;
+                }
+            };
+
+            let response = if request_line.starts_with("GET /metrics") {
+                let body = crate::metrics::render();
+                format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\n\r\n{}",
+                    body.len(),
+                    body
+                )
+            } else {
+                "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n".to_string()
+            };
+
+            if let Err(e) = socket.write_all(response.as_bytes()).await {
+                tracing::error!(error = ?e, "Failed to write /metrics response");
+            }
+        });
+    }
+}